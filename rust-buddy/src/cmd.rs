@@ -0,0 +1,138 @@
+use std::fmt;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// Captured output of a command run via [`AutoRun::run_capture`].
+pub struct CommandOutput {
+    pub success: bool,
+    pub status_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// A command that exited unsuccessfully. Carries the exit code so `main` can
+/// propagate it to the shell instead of collapsing every failure to `1`.
+#[derive(Debug)]
+pub struct CommandFailed {
+    command: String,
+    code: i32,
+}
+
+impl fmt::Display for CommandFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` exited with code {}", self.command, self.code)
+    }
+}
+
+impl std::error::Error for CommandFailed {}
+
+impl CommandFailed {
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+}
+
+/// Extension trait that runs a [`Command`] the way `buddy` expects a proxied
+/// invocation to behave: a non-zero exit surfaces as a real error (instead of
+/// a `println!` while the caller still returns `Ok(())`), and the error
+/// renders the full command line so it's obvious what actually failed.
+pub trait AutoRun {
+    /// Run the command, inheriting stdio, and fail loudly if it didn't exit successfully.
+    fn run(&mut self) -> Result<()>;
+
+    /// Run the command capturing stdout/stderr instead of inheriting them.
+    fn run_capture(&mut self) -> Result<CommandOutput>;
+}
+
+impl AutoRun for Command {
+    fn run(&mut self) -> Result<()> {
+        let description = describe(self);
+        let status = self
+            .status()
+            .with_context(|| format!("failed to execute `{description}`"))?;
+
+        if !status.success() {
+            return Err(CommandFailed {
+                command: description,
+                code: status.code().unwrap_or(1),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn run_capture(&mut self) -> Result<CommandOutput> {
+        let description = describe(self);
+        let output = self
+            .output()
+            .with_context(|| format!("failed to execute `{description}`"))?;
+
+        Ok(CommandOutput {
+            success: output.status.success(),
+            status_code: output.status.code().unwrap_or(1),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Render a command's program, args, and (if set) working directory as a
+/// single human-readable line for error messages.
+fn describe(command: &Command) -> String {
+    let mut parts = vec![command.get_program().to_string_lossy().into_owned()];
+    parts.extend(command.get_args().map(|a| a.to_string_lossy().into_owned()));
+    let mut description = parts.join(" ");
+
+    if let Some(dir) = command.get_current_dir() {
+        description.push_str(&format!(" (in {})", dir.display()));
+    }
+
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_succeeds_for_a_successful_command() {
+        assert!(Command::new("true").run().is_ok());
+    }
+
+    #[test]
+    fn run_fails_with_command_failed_carrying_the_exit_code() {
+        let err = Command::new("sh").args(["-c", "exit 7"]).run().unwrap_err();
+
+        let failed = err
+            .downcast_ref::<CommandFailed>()
+            .expect("error should be a CommandFailed");
+        assert_eq!(failed.code(), 7);
+    }
+
+    #[test]
+    fn run_capture_reports_stdout_stderr_and_status() {
+        let output = Command::new("sh")
+            .args(["-c", "echo out; echo err >&2; exit 3"])
+            .run_capture()
+            .unwrap();
+
+        assert!(!output.success);
+        assert_eq!(output.status_code, 3);
+        assert_eq!(output.stdout.trim(), "out");
+        assert_eq!(output.stderr.trim(), "err");
+    }
+
+    #[test]
+    fn describe_includes_program_args_and_current_dir() {
+        let mut command = Command::new("echo");
+        command.arg("hello");
+        command.current_dir(std::env::temp_dir());
+
+        let description = describe(&command);
+
+        assert!(description.starts_with("echo hello"));
+        assert!(description.contains(&std::env::temp_dir().display().to_string()));
+    }
+}
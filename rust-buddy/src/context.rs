@@ -0,0 +1,240 @@
+use std::cell::OnceCell;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cmd::AutoRun;
+use crate::discovery::{self, StacksProject};
+
+/// Snapshot of a git repository's state, computed lazily and cached for the
+/// lifetime of a single `buddy` invocation.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitState {
+    /// `None` exactly when `detached` is set. An unborn branch (no commit
+    /// yet) still reports its name here.
+    pub branch: Option<String>,
+    pub dirty: bool,
+    pub detached: bool,
+    pub rebase_in_progress: bool,
+}
+
+/// Execution context shared across subcommands: where we are, which project
+/// we're in, and (lazily) what git looks like from here. Built once in `main`
+/// and shared by the subcommands, so the working directory and enclosing
+/// project are resolved once; `cd` still runs its own `discovery::discover`
+/// from `current_dir` to find sibling/child projects by name.
+pub struct Context {
+    /// The real current directory, as returned by the OS.
+    pub current_dir: PathBuf,
+    /// The directory the user "thinks" they're in — honors `$PWD` (which
+    /// preserves symlinks) so proxied commands keep the path the user typed.
+    pub logical_dir: PathBuf,
+    /// The enclosing Stacks project, if any.
+    pub project: Option<StacksProject>,
+    git_state: OnceCell<Option<GitState>>,
+}
+
+impl Context {
+    pub fn new() -> Result<Self> {
+        let current_dir = env::current_dir()?;
+        let logical_dir = logical_dir(&current_dir);
+        let project = discovery::discover_upward(&current_dir);
+
+        Ok(Self {
+            current_dir,
+            logical_dir,
+            project,
+            git_state: OnceCell::new(),
+        })
+    }
+
+    /// The directory proxied `buddy` invocations should run in: the
+    /// discovered project root, falling back to `logical_dir` (rather than the
+    /// canonicalized `current_dir`) so a proxy started from a symlinked path
+    /// the user actually typed doesn't get silently resolved out from under it.
+    pub fn run_dir(&self) -> &Path {
+        self.project
+            .as_ref()
+            .map(|p| p.root.as_path())
+            .unwrap_or(&self.logical_dir)
+    }
+
+    /// Lazily computed git state for `current_dir`, cached after first use so
+    /// repeated callers (e.g. `info`, future prompt code) don't each shell out.
+    pub fn git_state(&self) -> Option<&GitState> {
+        self.git_state
+            .get_or_init(|| compute_git_state(&self.current_dir))
+            .as_ref()
+    }
+}
+
+fn logical_dir(current_dir: &Path) -> PathBuf {
+    env::var_os("PWD")
+        .map(PathBuf::from)
+        .filter(|pwd| canonicalizes_to(pwd, current_dir))
+        .unwrap_or_else(|| current_dir.to_path_buf())
+}
+
+fn canonicalizes_to(pwd: &Path, current_dir: &Path) -> bool {
+    std::fs::canonicalize(pwd)
+        .map(|canon| canon == current_dir)
+        .unwrap_or(false)
+}
+
+fn compute_git_state(dir: &Path) -> Option<GitState> {
+    let git_dir_output = Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .current_dir(dir)
+        .run_capture()
+        .ok()?;
+
+    if !git_dir_output.success {
+        return None;
+    }
+
+    let git_dir = dir.join(git_dir_output.stdout.trim());
+
+    // `symbolic-ref` only succeeds when HEAD points at a branch (including an
+    // unborn one before the first commit); a detached HEAD makes it fail.
+    let branch_output = Command::new("git")
+        .args(["symbolic-ref", "-q", "--short", "HEAD"])
+        .current_dir(dir)
+        .run_capture()
+        .ok()?;
+
+    let (branch, detached) = if branch_output.success {
+        (Some(branch_output.stdout.trim().to_string()), false)
+    } else {
+        (None, true)
+    };
+
+    let status_output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(dir)
+        .run_capture()
+        .ok()?;
+
+    let dirty = !status_output.stdout.trim().is_empty();
+
+    let rebase_in_progress = git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists();
+
+    Some(GitState {
+        branch,
+        dirty,
+        detached,
+        rebase_in_progress,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Create (and clear) a throwaway directory under the OS temp dir, unique
+    /// per test so parallel runs don't collide.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("buddy-context-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn temp_repo(name: &str) -> PathBuf {
+        let dir = temp_dir(name);
+
+        Command::new("git")
+            .args(["init", "-q", "--initial-branch=main"])
+            .current_dir(&dir)
+            .run()
+            .unwrap();
+        Command::new("git")
+            .args(["config", "user.email", "buddy-test@example.com"])
+            .current_dir(&dir)
+            .run()
+            .unwrap();
+        Command::new("git")
+            .args(["config", "user.name", "buddy-test"])
+            .current_dir(&dir)
+            .run()
+            .unwrap();
+
+        dir
+    }
+
+    fn commit(dir: &Path, contents: &str) {
+        fs::write(dir.join("file.txt"), contents).unwrap();
+        Command::new("git").args(["add", "-A"]).current_dir(dir).run().unwrap();
+        Command::new("git")
+            .args(["commit", "-q", "-m", contents])
+            .current_dir(dir)
+            .run()
+            .unwrap();
+    }
+
+    fn head_sha(dir: &Path) -> String {
+        Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(dir)
+            .run_capture()
+            .unwrap()
+            .stdout
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn compute_git_state_returns_none_outside_a_repository() {
+        let dir = temp_dir("norepo");
+
+        assert!(compute_git_state(&dir).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compute_git_state_reports_branch_and_clean_status() {
+        let dir = temp_repo("clean");
+        commit(&dir, "initial");
+
+        let state = compute_git_state(&dir).expect("should detect a repository");
+
+        assert_eq!(state.branch.as_deref(), Some("main"));
+        assert!(!state.dirty);
+        assert!(!state.detached);
+        assert!(!state.rebase_in_progress);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compute_git_state_reports_dirty_when_there_are_uncommitted_changes() {
+        let dir = temp_repo("dirty");
+        commit(&dir, "initial");
+        fs::write(dir.join("file.txt"), "changed").unwrap();
+
+        let state = compute_git_state(&dir).expect("should detect a repository");
+
+        assert!(state.dirty);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compute_git_state_detects_detached_head() {
+        let dir = temp_repo("detached");
+        commit(&dir, "initial");
+        let sha = head_sha(&dir);
+        Command::new("git").args(["checkout", "-q", &sha]).current_dir(&dir).run().unwrap();
+
+        let state = compute_git_state(&dir).expect("should detect a repository");
+
+        assert!(state.detached);
+        assert_eq!(state.branch, None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
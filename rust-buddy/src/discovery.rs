@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use walkdir::WalkDir;
+
+/// Relative path that marks the root of a Stacks project, mirroring the probe
+/// `create_new_project` already uses to decide whether it's sitting inside one.
+const PROJECT_MARKER: &str = "storage/framework/core/buddy";
+
+/// Directory names that are never worth descending into while searching
+/// downward for sibling/child projects.
+const EXCLUDED_DIRS: &[&str] = &["node_modules", ".git", "target", "vendor"];
+
+/// How many levels deep `discover` is willing to search below the starting
+/// directory before giving up.
+const MAX_DOWNWARD_DEPTH: usize = 6;
+
+/// A Stacks project found on disk.
+#[derive(Debug, Clone)]
+pub struct StacksProject {
+    /// Directory containing `storage/framework/core/buddy`.
+    pub root: PathBuf,
+    /// The project's directory name, used to match against `buddy cd <name>`.
+    pub name: String,
+}
+
+impl StacksProject {
+    fn from_root(root: PathBuf) -> Self {
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Self { root, name }
+    }
+
+    fn is_project_root(dir: &Path) -> bool {
+        dir.join(PROJECT_MARKER).exists()
+    }
+}
+
+/// Walk upward from `start`, returning the nearest enclosing Stacks project, if any.
+pub fn discover_upward(start: &Path) -> Option<StacksProject> {
+    let mut dir = start.to_path_buf();
+
+    loop {
+        if StacksProject::is_project_root(&dir) {
+            return Some(StacksProject::from_root(dir));
+        }
+
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// Walk downward from `start`, bounded to `MAX_DOWNWARD_DEPTH`, collecting every
+/// Stacks project found. Skips `node_modules`, `.git`, `target` and anything
+/// listed in a directory's own `.gitignore`, so it never degenerates into the
+/// full-filesystem scan the old `WalkDir::new("/")` did.
+pub fn discover_downward(start: &Path) -> Vec<StacksProject> {
+    let mut projects = Vec::new();
+
+    for entry in WalkDir::new(start)
+        .max_depth(MAX_DOWNWARD_DEPTH)
+        .into_iter()
+        // Only exclude descendants, never `start` itself — otherwise a start
+        // directory that happens to be named e.g. `target` would skip the
+        // whole walk instead of just declining to descend into a nested one.
+        .filter_entry(|e| e.depth() == 0 || !is_excluded(e.path()))
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+
+        if path.is_dir() && StacksProject::is_project_root(path) {
+            projects.push(StacksProject::from_root(path.to_path_buf()));
+        }
+    }
+
+    projects
+}
+
+fn is_excluded(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+
+    EXCLUDED_DIRS.contains(&name) || is_gitignored(path)
+}
+
+/// Crude `.gitignore` check: does the entry's own name appear (bare or with a
+/// trailing slash) in the `.gitignore` sitting next to it?
+fn is_gitignored(path: &Path) -> bool {
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+
+    let Ok(contents) = std::fs::read_to_string(parent.join(".gitignore")) else {
+        return false;
+    };
+
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .any(|line| line == name || line == format!("{name}/"))
+}
+
+/// Discover every Stacks project reachable from `start`: walk upward first to
+/// find the enclosing project (if `start` is inside one), then walk downward
+/// (bounded) to pick up sibling/child projects, so callers like `cd` have
+/// real project names to match against instead of a path built with string
+/// formatting.
+pub fn discover(start: &Path) -> Result<Vec<StacksProject>> {
+    let mut projects = Vec::new();
+
+    if let Some(upward) = discover_upward(start) {
+        projects.push(upward);
+    }
+
+    for project in discover_downward(start) {
+        if !projects.iter().any(|p: &StacksProject| p.root == project.root) {
+            projects.push(project);
+        }
+    }
+
+    Ok(projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Create (and clear) a throwaway directory under the OS temp dir, unique
+    /// per test so parallel runs don't collide.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("buddy-discovery-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn make_project(root: &Path) {
+        fs::create_dir_all(root.join(PROJECT_MARKER)).unwrap();
+    }
+
+    #[test]
+    fn discover_upward_finds_enclosing_project() {
+        let root = temp_dir("upward");
+        make_project(&root);
+        let nested = root.join("app/src");
+        fs::create_dir_all(&nested).unwrap();
+
+        let found = discover_upward(&nested).expect("should find enclosing project");
+        assert_eq!(found.root, root);
+        assert_eq!(found.name, root.file_name().unwrap().to_string_lossy());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn discover_upward_returns_none_outside_any_project() {
+        let root = temp_dir("upward-none");
+
+        assert!(discover_upward(&root).is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn discover_downward_finds_nested_projects_but_skips_node_modules() {
+        let root = temp_dir("downward");
+        make_project(&root.join("real-project"));
+        make_project(&root.join("node_modules/fake-project"));
+
+        let found = discover_downward(&root);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "real-project");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn discover_downward_honors_gitignore() {
+        let root = temp_dir("gitignore");
+        fs::write(root.join(".gitignore"), "ignored-dir/\n").unwrap();
+        make_project(&root.join("ignored-dir"));
+        make_project(&root.join("kept-dir"));
+
+        let found = discover_downward(&root);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "kept-dir");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn gitignore_pattern_does_not_prefix_match_unrelated_directory() {
+        let root = temp_dir("gitignore-literal");
+        fs::write(root.join(".gitignore"), "build\n").unwrap();
+        make_project(&root.join("builder"));
+
+        let found = discover_downward(&root);
+
+        assert_eq!(
+            found.len(),
+            1,
+            "a literal gitignore entry ('build') shouldn't also exclude 'builder'"
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn discover_downward_still_finds_project_when_start_dir_itself_is_excluded_name() {
+        // `start` happening to be named like one of our own exclusions (e.g.
+        // `target`) must not skip the whole walk — only its descendants are
+        // subject to exclusion.
+        let root = temp_dir("excluded-root");
+        let start = root.join("target");
+        make_project(&start);
+
+        let found = discover_downward(&start);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].root, start);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cmd::AutoRun;
+use crate::context::GitState;
+use crate::discovery::StacksProject;
+
+/// Everything `buddy info` reports: the discovered project (if any), the
+/// runtimes available to it, and the git state of the current directory.
+#[derive(Serialize)]
+pub struct InfoReport {
+    pub project: Option<ProjectInfo>,
+    pub runtime: RuntimeInfo,
+    pub git: Option<GitState>,
+}
+
+#[derive(Serialize)]
+pub struct ProjectInfo {
+    pub root: String,
+    pub framework: Framework,
+    pub version: Option<String>,
+    pub package_manager: Option<PackageManager>,
+    pub dependencies: Vec<DependencyInfo>,
+    /// Set when `package.json` was missing, unreadable, or not valid JSON — a
+    /// project only needs the `storage/framework/core/buddy` marker, not a
+    /// root manifest, so this is expected to happen sometimes.
+    pub manifest_error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Framework {
+    Stacks,
+    Bare,
+}
+
+impl fmt::Display for Framework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Framework::Stacks => write!(f, "stacks"),
+            Framework::Bare => write!(f, "bare"),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Bun,
+    Yarn,
+    Npm,
+    Pnpm,
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageManager::Bun => write!(f, "bun"),
+            PackageManager::Yarn => write!(f, "yarn"),
+            PackageManager::Npm => write!(f, "npm"),
+            PackageManager::Pnpm => write!(f, "pnpm"),
+        }
+    }
+}
+
+/// A single dependency, with both the range declared in `package.json` and
+/// (when the lockfile format is parseable) the version actually resolved.
+#[derive(Serialize)]
+pub struct DependencyInfo {
+    pub name: String,
+    pub declared: String,
+    pub resolved: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RuntimeInfo {
+    pub node: Option<String>,
+    pub bun: Option<String>,
+}
+
+#[derive(serde::Deserialize, Clone)]
+struct PackageManifest {
+    version: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(rename = "devDependencies", default)]
+    dev_dependencies: BTreeMap<String, String>,
+}
+
+/// Build an [`InfoReport`] for the given project (or just the runtime info, if
+/// no project was discovered). `git` should come from [`crate::context::Context::git_state`]
+/// so callers share the one lazily-computed snapshot instead of shelling out again.
+pub fn gather(project: Option<&StacksProject>, git: Option<GitState>) -> Result<InfoReport> {
+    let runtime = RuntimeInfo {
+        node: tool_version("node"),
+        bun: tool_version("bun"),
+    };
+
+    let project = project.map(gather_project);
+
+    Ok(InfoReport { project, runtime, git })
+}
+
+fn tool_version(tool: &str) -> Option<String> {
+    let output = Command::new(tool).arg("--version").run_capture().ok()?;
+
+    if !output.success {
+        eprintln!(
+            "warning: `{tool} --version` exited with code {}: {}",
+            output.status_code,
+            output.stderr.trim()
+        );
+        return None;
+    }
+
+    Some(output.stdout.trim().to_string())
+}
+
+fn gather_project(project: &StacksProject) -> ProjectInfo {
+    let package_manager = detect_package_manager(&project.root);
+
+    let manifest = match read_manifest(&project.root) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            return ProjectInfo {
+                root: project.root.display().to_string(),
+                framework: Framework::Bare,
+                version: None,
+                package_manager,
+                dependencies: Vec::new(),
+                manifest_error: Some(err.to_string()),
+            };
+        }
+    };
+
+    let framework = if manifest
+        .dependencies
+        .keys()
+        .chain(manifest.dev_dependencies.keys())
+        .any(|name| name.starts_with("@stacksjs/"))
+    {
+        Framework::Stacks
+    } else {
+        Framework::Bare
+    };
+
+    let resolved = resolve_lockfile_versions(&project.root, package_manager);
+
+    let mut declared = manifest.dependencies.clone();
+    declared.extend(manifest.dev_dependencies.clone());
+
+    let dependencies = declared
+        .into_iter()
+        .map(|(name, declared)| {
+            let resolved = resolved.get(&name).cloned();
+            DependencyInfo { name, declared, resolved }
+        })
+        .collect();
+
+    ProjectInfo {
+        root: project.root.display().to_string(),
+        framework,
+        version: manifest.version,
+        package_manager,
+        dependencies,
+        manifest_error: None,
+    }
+}
+
+/// Read and parse the project's `package.json`. Kept separate so its errors
+/// (missing file, bad JSON) can be downgraded to a `manifest_error` note
+/// instead of failing the whole `buddy info` report.
+fn read_manifest(root: &Path) -> Result<PackageManifest> {
+    let contents = fs::read_to_string(root.join("package.json"))?;
+    let manifest = serde_json::from_str(&contents)?;
+    Ok(manifest)
+}
+
+fn detect_package_manager(root: &Path) -> Option<PackageManager> {
+    if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+        Some(PackageManager::Bun)
+    } else if root.join("yarn.lock").exists() {
+        Some(PackageManager::Yarn)
+    } else if root.join("pnpm-lock.yaml").exists() {
+        Some(PackageManager::Pnpm)
+    } else if root.join("package-lock.json").exists() {
+        Some(PackageManager::Npm)
+    } else {
+        None
+    }
+}
+
+/// Parse resolved dependency versions out of the lockfile of the detected
+/// package manager. Only `package-lock.json` (npm) and `yarn.lock` (yarn) are
+/// parsed; for bun (`bun.lockb` is a binary format) and pnpm this returns an
+/// empty map, so only the declared ranges are reported.
+fn resolve_lockfile_versions(root: &Path, manager: Option<PackageManager>) -> BTreeMap<String, String> {
+    match manager {
+        Some(PackageManager::Npm) => resolve_package_lock(root),
+        Some(PackageManager::Yarn) => resolve_yarn_lock(root),
+        _ => BTreeMap::new(),
+    }
+}
+
+fn resolve_package_lock(root: &Path) -> BTreeMap<String, String> {
+    let Ok(contents) = fs::read_to_string(root.join("package-lock.json")) else {
+        return BTreeMap::new();
+    };
+
+    parse_package_lock(&contents)
+}
+
+fn parse_package_lock(contents: &str) -> BTreeMap<String, String> {
+    let mut resolved = BTreeMap::new();
+
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(contents) else {
+        return resolved;
+    };
+
+    if let Some(packages) = value.get("packages").and_then(|p| p.as_object()) {
+        for (path, info) in packages {
+            let Some(name) = path.strip_prefix("node_modules/") else {
+                continue;
+            };
+
+            if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
+                resolved.insert(name.to_string(), version.to_string());
+            }
+        }
+    }
+
+    resolved
+}
+
+/// Small `yarn.lock` scanner: looks for `name@range:` headers followed by a
+/// `version "x.y.z"` line. Good enough for a diagnostics report without
+/// pulling in a full lockfile parser.
+fn resolve_yarn_lock(root: &Path) -> BTreeMap<String, String> {
+    let Ok(contents) = fs::read_to_string(root.join("yarn.lock")) else {
+        return BTreeMap::new();
+    };
+
+    parse_yarn_lock(&contents)
+}
+
+fn parse_yarn_lock(contents: &str) -> BTreeMap<String, String> {
+    let mut resolved = BTreeMap::new();
+    let mut current_name: Option<String> = None;
+
+    for line in contents.lines() {
+        if !line.starts_with(' ') && line.ends_with(':') {
+            current_name = line
+                .trim_end_matches(':')
+                .split(", ")
+                .next()
+                .and_then(|spec| spec.trim_start_matches('"').rsplit_once('@'))
+                .map(|(name, _)| name.to_string());
+        } else if let Some(name) = &current_name {
+            if let Some(version) = line.trim().strip_prefix("version ") {
+                resolved.insert(name.clone(), version.trim_matches('"').to_string());
+                current_name = None;
+            }
+        }
+    }
+
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yarn_lock_resolves_scoped_package_name() {
+        let contents = r#"
+"@scope/pkg@^1.0.0", "@scope/pkg@^1.1.0":
+  version "1.1.0"
+  resolved "https://example.com/@scope/pkg-1.1.0.tgz"
+
+lodash@^4.17.21:
+  version "4.17.21"
+  resolved "https://example.com/lodash-4.17.21.tgz"
+"#;
+
+        let resolved = parse_yarn_lock(contents);
+
+        assert_eq!(resolved.get("@scope/pkg").map(String::as_str), Some("1.1.0"));
+        assert_eq!(resolved.get("lodash").map(String::as_str), Some("4.17.21"));
+    }
+
+    #[test]
+    fn package_lock_resolves_nested_scoped_package_path() {
+        let contents = r#"
+{
+  "name": "root",
+  "packages": {
+    "": { "name": "root" },
+    "node_modules/@scope/pkg": { "version": "2.3.4" },
+    "node_modules/lodash": { "version": "4.17.21" }
+  }
+}
+"#;
+
+        let resolved = parse_package_lock(contents);
+
+        assert_eq!(resolved.get("@scope/pkg").map(String::as_str), Some("2.3.4"));
+        assert_eq!(resolved.get("lodash").map(String::as_str), Some("4.17.21"));
+    }
+}
@@ -1,12 +1,21 @@
 use std::env;
 use std::fs;
-use std::path::{Path, PathBuf};
 use std::process::{Command, exit};
 use clap::{Parser, Subcommand};
-use anyhow::{Result, Context};
-use walkdir::WalkDir;
+use anyhow::{Result, Context as _};
 use serde::Deserialize;
 
+mod cmd;
+mod context;
+mod discovery;
+mod info;
+mod shell;
+
+use cmd::{AutoRun, CommandFailed};
+use context::Context;
+use discovery::StacksProject;
+use shell::Shell;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -27,11 +36,25 @@ enum Commands {
     Cd {
         /// Project name
         project: String,
+        /// Print only the resolved path, for shell integration (see `buddy init`)
+        #[arg(long, hide = true)]
+        print_cd: bool,
     },
     /// Show the version of the Stacks CLI
     Version,
     /// Show help information
     Help,
+    /// Print a shell integration snippet that lets `buddy cd` change the caller's directory
+    Init {
+        /// Shell to generate the snippet for
+        shell: Shell,
+    },
+    /// Show framework, runtime, and dependency version diagnostics
+    Info {
+        /// Emit the report as JSON instead of a human-readable summary
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Deserialize)]
@@ -39,15 +62,28 @@ struct PackageJson {
     version: String,
 }
 
-fn main() -> Result<()> {
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("Error: {err:#}");
+
+        if let Some(failed) = err.downcast_ref::<CommandFailed>() {
+            exit(failed.code());
+        }
+
+        exit(1);
+    }
+}
+
+fn run() -> Result<()> {
     let cli = Cli::parse();
+    let ctx = Context::new()?;
 
     match &cli.command {
         Some(Commands::New { name }) => {
-            create_new_project(name)?;
+            create_new_project(&ctx, name)?;
         }
-        Some(Commands::Cd { project }) => {
-            change_directory(project)?;
+        Some(Commands::Cd { project, print_cd }) => {
+            change_directory(&ctx, project, *print_cd)?;
         }
         Some(Commands::Version) => {
             print_version()?;
@@ -55,9 +91,15 @@ fn main() -> Result<()> {
         Some(Commands::Help) => {
             print_help();
         }
+        Some(Commands::Init { shell }) => {
+            print!("{}", shell::init_script(*shell));
+        }
+        Some(Commands::Info { json }) => {
+            print_info(&ctx, *json)?;
+        }
         None => {
             // If no command is provided, try to proxy to the buddy script
-            if !proxy_command()? {
+            if !proxy_command(&ctx)? {
                 print_help();
             }
         }
@@ -73,6 +115,8 @@ fn print_help() {
     println!("  new, create       Create a new Stacks project");
     println!("  cd <project>      Change the current working directory to a different Stacks project");
     println!("  version           Show the version of the Stacks CLI");
+    println!("  init <shell>      Print a shell integration snippet (bash, zsh, fish, powershell)");
+    println!("  info              Show framework, runtime, and dependency version diagnostics");
     println!("  help              Show this help message");
     println!("");
 }
@@ -108,102 +152,123 @@ fn get_version() -> Result<String> {
     Ok("0.0.0".to_string())
 }
 
-fn create_new_project(_name: &str) -> Result<()> {
-    let buddy_cli = "buddy";
+fn create_new_project(ctx: &Context, _name: &str) -> Result<()> {
+    let run_dir = ctx.run_dir();
+    let buddy_cli = run_dir.join("buddy");
 
-    if Path::new(buddy_cli).exists() {
+    if buddy_cli.exists() {
         let args: Vec<String> = env::args().skip(1).collect();
-        let status = Command::new(buddy_cli)
-            .args(args)
-            .status()
-            .context("Failed to execute buddy command")?;
-
-        if !status.success() {
-            println!("Command failed with exit code: {:?}", status.code());
-        }
+        Command::new(&buddy_cli).args(args).current_dir(run_dir).run()?;
         return Ok(());
     }
 
-    let mut current_dir = env::current_dir()?;
-    let mut found = false;
-
-    while current_dir.as_os_str() != "/" {
-        let buddy_path = current_dir.join("storage/framework/core/buddy");
-        if buddy_path.exists() {
-            found = true;
-            break;
-        }
-
-        if !current_dir.pop() {
-            break;
-        }
-    }
-
-    if !found {
+    if ctx.project.is_none() {
         println!("No stacks project found. Do you want to create a new stacks project?");
         // TODO: add prompt for user input
         exit(1);
     }
 
     let args: Vec<String> = env::args().skip(1).collect();
-    let status = Command::new("./buddy")
-        .arg("new")
-        .args(args)
-        .status()
-        .context("Failed to execute ./buddy command")?;
-
-    if !status.success() {
-        println!("Command failed with exit code: {:?}", status.code());
-    }
+    Command::new("./buddy").arg("new").args(args).current_dir(run_dir).run()?;
 
     Ok(())
 }
 
-fn change_directory(project: &str) -> Result<()> {
-    let project_path = find_project_path("/", project)?;
+fn change_directory(ctx: &Context, project: &str, print_cd: bool) -> Result<()> {
+    let projects = discovery::discover(&ctx.current_dir)?;
+    let found = projects.iter().find(|p: &&StacksProject| p.name == project);
+
+    if print_cd {
+        // Machine-readable mode for the `buddy` shell wrapper: print only the
+        // resolved path (nothing else) so it can be safely captured and `cd`-ed into.
+        if let Some(found) = found {
+            println!("{}", found.root.display());
+        }
+        return Ok(());
+    }
 
-    if let Some(path) = project_path {
-        println!("Project found at {}.", path.display());
-        println!("Run 'cd {}' to navigate to the project directory.", path.display());
-    } else {
-        println!("Project directory not found.");
+    match found {
+        Some(found) => {
+            println!("Project found at {}.", found.root.display());
+            println!("Run 'cd {}' to navigate to the project directory.", found.root.display());
+        }
+        None => {
+            println!("Project directory not found.");
+        }
     }
 
     Ok(())
 }
 
-fn find_project_path(base: &str, target: &str) -> Result<Option<PathBuf>> {
-    let target_path = format!("{}/storage/framework/core/buddy/", target);
+fn print_info(ctx: &Context, json: bool) -> Result<()> {
+    let report = info::gather(ctx.project.as_ref(), ctx.git_state().cloned())?;
 
-    for entry in WalkDir::new(base)
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok()) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    match &report.project {
+        Some(project) => {
+            println!("Project:         {}", project.root);
+            println!("Framework:       {}", project.framework);
 
-        let path = entry.path();
-        if path.is_dir() {
-            println!("Checking {}...", path.display());
+            if let Some(version) = &project.version {
+                println!("Version:         {}", version);
+            }
+
+            if let Some(manager) = project.package_manager {
+                println!("Package manager: {}", manager);
+            }
 
-            if path.to_string_lossy().contains(&target_path) {
-                return Ok(Some(path.to_path_buf()));
+            if let Some(error) = &project.manifest_error {
+                println!("package.json:    unreadable ({error})");
+            }
+
+            if !project.dependencies.is_empty() {
+                println!("Dependencies:");
+                for dep in &project.dependencies {
+                    match &dep.resolved {
+                        Some(resolved) => println!("  {} {} (resolved {})", dep.name, dep.declared, resolved),
+                        None => println!("  {} {}", dep.name, dep.declared),
+                    }
+                }
             }
         }
+        None => println!("No stacks project found."),
     }
 
-    Ok(None)
+    println!();
+    println!("Runtime:");
+    println!("  node: {}", report.runtime.node.as_deref().unwrap_or("not found"));
+    println!("  bun:  {}", report.runtime.bun.as_deref().unwrap_or("not found"));
+
+    println!();
+    match &report.git {
+        Some(git) => {
+            println!("Git:");
+            match (&git.branch, git.detached) {
+                (Some(branch), _) => println!("  branch: {}", branch),
+                (None, true) => println!("  branch: (detached HEAD)"),
+                (None, false) => println!("  branch: (none)"),
+            }
+            println!("  status: {}", if git.dirty { "dirty" } else { "clean" });
+            if git.rebase_in_progress {
+                println!("  rebase in progress");
+            }
+        }
+        None => println!("Git: not a repository"),
+    }
+
+    Ok(())
 }
 
-fn proxy_command() -> Result<bool> {
-    if Path::new("./buddy").exists() {
-        let args: Vec<String> = env::args().skip(1).collect();
-        let status = Command::new("./buddy")
-            .args(args)
-            .status()
-            .context("Failed to execute ./buddy command")?;
+fn proxy_command(ctx: &Context) -> Result<bool> {
+    let run_dir = ctx.run_dir();
 
-        if !status.success() {
-            println!("Command failed with exit code: {:?}", status.code());
-        }
+    if run_dir.join("buddy").exists() {
+        let args: Vec<String> = env::args().skip(1).collect();
+        Command::new("./buddy").args(args).current_dir(run_dir).run()?;
 
         return Ok(true);
     }
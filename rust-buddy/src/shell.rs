@@ -0,0 +1,73 @@
+use clap::ValueEnum;
+
+/// Shells `buddy init` knows how to emit an integration snippet for.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// Return the shell snippet that defines a `buddy` wrapper function. The
+/// wrapper intercepts `buddy cd <project>`, resolves the target directory by
+/// re-invoking the real binary with `--print-cd` (which prints only the
+/// resolved path, nothing else), and `cd`s the *current* shell into it —
+/// everything else is passed straight through to the real binary.
+pub fn init_script(shell: Shell) -> String {
+    match shell {
+        Shell::Bash | Shell::Zsh => {
+            r#"buddy() {
+    if [ "$1" = "cd" ]; then
+        local dir
+        dir=$(command buddy cd "$2" --print-cd)
+        if [ -n "$dir" ]; then
+            builtin cd "$dir"
+        else
+            command buddy cd "$2"
+        fi
+    else
+        command buddy "$@"
+    fi
+}
+"#
+            .to_string()
+        }
+        Shell::Fish => {
+            r#"function buddy
+    if test "$argv[1]" = "cd"
+        set -l dir (command buddy cd $argv[2] --print-cd)
+        if test -n "$dir"
+            builtin cd "$dir"
+        else
+            command buddy cd $argv[2]
+        end
+    else
+        command buddy $argv
+    end
+end
+"#
+            .to_string()
+        }
+        Shell::Powershell => {
+            r#"function buddy {
+    param([Parameter(ValueFromRemainingArguments = $true)] $Args)
+
+    $exe = (Get-Command buddy -CommandType Application | Select-Object -First 1).Source
+
+    if ($Args[0] -eq "cd") {
+        $dir = (& $exe cd $Args[1] --print-cd)
+        if ($dir) {
+            Set-Location $dir
+        } else {
+            & $exe cd $Args[1]
+        }
+    } else {
+        & $exe @Args
+    }
+}
+"#
+            .to_string()
+        }
+    }
+}